@@ -10,10 +10,13 @@ mod wu;
 use wu::source::*;
 use wu::lexer::*;
 use wu::parser::{ Parser, ExpressionNode, Expression, };
-use wu::visitor::Visitor;
+use wu::visitor::{ Visitor, SymTab, TypeTab, };
+use wu::optimizer;
 use wu::interpreter::*;
 
 use std::env;
+use std::fs::File;
+use std::io::{ self, Read, Write, BufRead, };
 
 fn run(content: &str) {
   let source = Source::from("main.rs/testing.wu", content.lines().map(|x| x.into()).collect::<Vec<String>>());
@@ -35,12 +38,14 @@ fn run(content: &str) {
 
   match parser.parse() {
     Ok(ast) => {
-      println!("{:#?}", ast);      
+      println!("{:#?}", ast);
+
+      let mut visitor = Visitor::new(&source, &ast);
 
-      let mut visitor = Visitor::new(&source, &ast);      
- 
       match visitor.visit() {
-        Ok(_) => {          
+        Ok(_) => {
+          let ast = optimizer::optimize(&ast);
+
           let mut compiler = Compiler::new(&mut visitor);
 
           match compiler.compile(&ast) {
@@ -66,75 +71,157 @@ fn run(content: &str) {
   }
 }
 
-fn main() {
-  let test1 = r#"
-a: int   = 123
-b: float = .123
-c: char  = 'b'
-d: char  = 'a'
-e: str   = r"rawwww"
-f: bool  = true
+// true once a buffer looks like it ended in the middle of a construct, i.e. an unclosed block,
+// set, or a trailing `->` — the REPL keeps prompting for more lines in that case instead of
+// reporting a parse error. Bracket-like characters inside a string or char literal (e.g.
+// `s: str = "("`) don't count, so a literal containing one doesn't force an extra blank line.
+fn looks_incomplete(buffer: &str) -> bool {
+  let trimmed = buffer.trim_end();
+
+  if trimmed.is_empty() || trimmed.ends_with("->") {
+    return !trimmed.is_empty()
+  }
+
+  let mut depth     = 0i32;
+  let mut in_string = false;
+  let mut in_char   = false;
+  let mut escaped   = false;
+
+  for c in trimmed.chars() {
+    if escaped {
+      escaped = false;
+      continue
+    }
+
+    match c {
+      '\\' if in_string || in_char => escaped = true,
+
+      '"'  if !in_char             => in_string = !in_string,
+      '\'' if !in_string           => in_char = !in_char,
+
+      '(' | '[' | '{' if !in_string && !in_char => depth += 1,
+      ')' | ']' | '}' if !in_string && !in_char => depth -= 1,
+
+      _ => (),
+    }
+  }
+
+  depth > 0
+}
+
+// interactive front-end used when no file is given on the command line: reads source a line at
+// a time, tokenizing/type-checking/compiling/running each complete entry while keeping the
+// `SymTab`/`TypeTab` from prior entries in scope, so `a := 10` on one line is still visible on
+// the next
+fn repl() {
+  println!("wu repl — ^D to quit");
+
+  let stdin = io::stdin();
+  let mut vm = VirtualMachine::new();
+
+  let mut symtab  = SymTab::global();
+  let mut typetab = TypeTab::global();
+
+  let mut buffer = String::new();
+
+  loop {
+    print!("{}", if buffer.is_empty() { "> " } else { "... " });
+    io::stdout().flush().ok();
 
-foo := f
+    let mut line = String::new();
 
-a: int:   123
-b: float: .123
-c: char:  '\n'
-d: char:  'a'
-e: str:   "raw"
-f: bool:  true
+    if stdin.lock().read_line(&mut line).unwrap_or(0) == 0 {
+      println!();
+      break
+    }
 
-bar :: b
+    let blank = line.trim().is_empty();
+    buffer.push_str(&line);
 
-hmm: int
-  "#;
+    if looks_incomplete(&buffer) && !blank {
+      continue
+    }
 
-  let test2 = r#"
-(a, b, c) := (1, 2, 3)
-(æ, ø): (int, str) = (1000, "world")
+    let source = Source::from("repl", buffer.lines().map(|x| x.into()).collect::<Vec<String>>());
+    let lexer  = Lexer::default(buffer.chars().collect(), &source);
 
-(grr): bool: false
-(bar): (float): .123
+    let mut tokens  = Vec::new();
+    let mut lex_err = false;
 
-(d, e, f, g) :: (1, "two", 3, 4, "hey")
+    for token_result in lexer {
+      match token_result {
+        Ok(token) => tokens.push(token),
+        Err(_)    => { lex_err = true; break },
+      }
+    }
 
-a
-b
-c
-d
-e
-f
-g
-grr
-æ
-ø
-  "#;
+    if lex_err {
+      buffer.clear();
+      continue
+    }
 
-  let test3 = r#"
-a: int  = 100
-b: bool = false
+    let tokens_ref = tokens.iter().map(|x| &*x).collect::<Vec<&Token>>();
+    let mut parser = Parser::new(tokens_ref, &source);
 
-c := .123
+    match parser.parse() {
+      Ok(ast) => {
+        buffer.clear();
 
-d: str: "communism essentially"
-e: str: r"you can't escape \n\n\n"
+        // hold onto the pre-entry state so a failed visit/compile doesn't leave a half-bound
+        // name registered for later entries while the VM never actually saw it pushed
+        let (prior_symtab, prior_typetab) = (symtab.clone(), typetab.clone());
 
-f :: 'a'
+        let mut visitor = Visitor::with_state(&source, &ast, symtab, typetab);
+        let mut committed = false;
 
-(g, h): (int, bool) = (1000, false)
+        if visitor.visit().is_ok() {
+          for statement in &ast {
+            if let Ok(t) = visitor.type_of(statement) {
+              println!(": {}", t)
+            }
+          }
 
-(one, two, three, four, five) := (1, "two", .3, '4', false)
+          let ast = optimizer::optimize(&ast);
+          let mut compiler = Compiler::new(&mut visitor);
 
-(foo): float =( (1/2) +  (1))
-  "#;
+          if compiler.compile(&ast).is_ok() {
+            vm.execute(compiler.bytecode.as_slice());
 
-  let test4 = r#"
-add :: (a: i128, b: i128) i128 -> a + b
+            println!("=> {:?}", vm.compute_stack.last());
 
-add(10, 10)
+            committed = true;
+          }
+        }
 
-((a: i128, b: i128) i128 -> a + b)(10, 20)
-  "#;
+        if committed {
+          symtab  = visitor.symtab;
+          typetab = visitor.typetab;
+        } else {
+          symtab  = prior_symtab;
+          typetab = prior_typetab;
+        }
+      },
 
-  run(&test4);
-}
\ No newline at end of file
+      Err(_) => if looks_incomplete(&buffer) {
+        continue
+      } else {
+        buffer.clear()
+      },
+    }
+  }
+}
+
+fn main() {
+  match env::args().nth(1) {
+    Some(path) => {
+      let mut file    = File::open(&path).expect("couldn't open source file");
+      let mut content = String::new();
+
+      file.read_to_string(&mut content).expect("couldn't read source file");
+
+      run(&content)
+    },
+
+    None => repl(),
+  }
+}
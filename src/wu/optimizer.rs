@@ -0,0 +1,201 @@
+use super::parser::{ Expression, ExpressionNode, Statement, StatementNode };
+
+// Runs after `Visitor::visit` and before compilation, folding the expression of each top-level
+// statement down to a simpler equivalent form. Only arithmetic is descended into: expressions
+// nested inside a function body, block, call or set are left as they are.
+pub fn optimize<'v>(ast: &[Statement<'v>]) -> Vec<Statement<'v>> {
+  ast.iter().map(fold_statement).collect()
+}
+
+fn fold_statement<'v>(statement: &Statement<'v>) -> Statement<'v> {
+  use self::StatementNode::*;
+
+  let node = match statement.node {
+    Expression(ref e) => Expression(fold_expression(e)),
+
+    Variable(ref t, ref left, ref right) => Variable(
+      t.to_owned(),
+      left.to_owned(),
+      right.as_ref().map(fold_expression),
+    ),
+
+    Constant(ref t, ref left, ref right) => Constant(t.to_owned(), left.to_owned(), fold_expression(right)),
+  };
+
+  Statement::new(node, statement.pos)
+}
+
+// Rewrites an arithmetic expression tree to a simpler equivalent one: literal operands are
+// folded, the identity rules below are applied, and a chain of `+`/`-` has its constants summed
+// and identical terms of opposite sign cancelled, e.g. `arg + 1 - arg + 2` collapses to `3`. Like
+// terms are not combined by coefficient, so `arg + arg - arg * 2` is left as it is. Only numeric
+// operands are touched, so string concatenation via `+` is left alone.
+pub fn fold_expression<'v>(expression: &Expression<'v>) -> Expression<'v> {
+  use self::ExpressionNode::*;
+
+  match expression.node {
+    Binary(ref left, ref op, ref right) => {
+      let left  = fold_expression(left);
+      let right = fold_expression(right);
+
+      if let (Some(a), Some(b)) = (as_literal(&left), as_literal(&right)) {
+        if let Some(folded) = fold_literal(a, op, b) {
+          return Expression::new(folded, expression.pos)
+        }
+      }
+
+      if is_pure(&left) && is_pure(&right) {
+        if let Some(simplified) = simplify_identity(&left, op, &right) {
+          return simplified
+        }
+
+        if op == "+" || op == "-" {
+          return canonicalize(&left, op, &right, expression.pos)
+        }
+      }
+
+      Expression::new(Binary(Box::new(left), op.to_owned(), Box::new(right)), expression.pos)
+    },
+
+    _ => expression.to_owned(),
+  }
+}
+
+// identity/cancellation rules drop one of the two operands outright, so they may only fire on
+// operands that can't carry a side effect — a literal, a plain variable reference, or nested
+// arithmetic over more of the same. A `Call`/`Field`/`Block`/... might run arbitrary code, so
+// e.g. `f() * 0` must still evaluate `f()` rather than collapsing straight to `0`.
+fn is_pure(e: &Expression) -> bool {
+  match e.node {
+    ExpressionNode::Number(_) | ExpressionNode::Identifier(_) => true,
+    ExpressionNode::Binary(ref left, _, ref right) => is_pure(left) && is_pure(right),
+    _ => false,
+  }
+}
+
+fn as_literal(e: &Expression) -> Option<f64> {
+  match e.node {
+    ExpressionNode::Number(n) => Some(n),
+    _ => None,
+  }
+}
+
+fn fold_literal<'v>(a: f64, op: &str, b: f64) -> Option<ExpressionNode<'v>> {
+  let folded = match op {
+    "+" => a + b,
+    "-" => a - b,
+    "*" => a * b,
+    "/" => a / b,
+    _   => return None,
+  };
+
+  Some(ExpressionNode::Number(folded))
+}
+
+// `x + 0`, `0 + x`, `x - 0`, `x * 1`, `1 * x`, `x * 0`, `x - x`
+fn simplify_identity<'v>(left: &Expression<'v>, op: &str, right: &Expression<'v>) -> Option<Expression<'v>> {
+  let zero = |e: &Expression| as_literal(e) == Some(0.0);
+  let one  = |e: &Expression| as_literal(e) == Some(1.0);
+
+  match op {
+    "+" if zero(left)  => Some(right.to_owned()),
+    "+" if zero(right) => Some(left.to_owned()),
+    "-" if zero(right) => Some(left.to_owned()),
+    "-" if structurally_equal(left, right) => Some(Expression::new(ExpressionNode::Number(0.0), left.pos)),
+    "*" if one(left)   => Some(right.to_owned()),
+    "*" if one(right)  => Some(left.to_owned()),
+    "*" if zero(left) || zero(right) => Some(Expression::new(ExpressionNode::Number(0.0), left.pos)),
+    _ => None,
+  }
+}
+
+// flattens a chain of `+`/`-` into signed terms, sums the constants into one and cancels
+// identical non-constant terms that appear with opposite signs
+fn canonicalize<'v>(left: &Expression<'v>, op: &str, right: &Expression<'v>, pos: (usize, usize)) -> Expression<'v> {
+  let mut terms = Vec::new();
+
+  flatten(left, 1, &mut terms);
+  flatten(right, if op == "+" { 1 } else { -1 }, &mut terms);
+
+  let mut constant = 0.0;
+  let mut rest: Vec<(i64, Expression<'v>)> = Vec::new();
+
+  for (sign, term) in terms {
+    if let Some(n) = as_literal(&term) {
+      constant += sign as f64 * n;
+    } else {
+      rest.push((sign, term));
+    }
+  }
+
+  // cancel `x` against `-x`
+  let mut cancelled = vec![false; rest.len()];
+
+  for i in 0 .. rest.len() {
+    if cancelled[i] {
+      continue
+    }
+
+    for j in i + 1 .. rest.len() {
+      if !cancelled[j] && rest[i].0 == -rest[j].0 && structurally_equal(&rest[i].1, &rest[j].1) {
+        cancelled[i] = true;
+        cancelled[j] = true;
+        break
+      }
+    }
+  }
+
+  let mut result = if constant != 0.0 {
+    Some(Expression::new(ExpressionNode::Number(constant), pos))
+  } else {
+    None
+  };
+
+  for (i, &(sign, ref term)) in rest.iter().enumerate() {
+    if cancelled[i] {
+      continue
+    }
+
+    result = Some(match result {
+      None => if sign < 0 {
+        Expression::new(
+          ExpressionNode::Binary(Box::new(Expression::new(ExpressionNode::Number(0.0), pos)), "-".to_owned(), Box::new(term.to_owned())),
+          pos,
+        )
+      } else {
+        term.to_owned()
+      },
+
+      Some(acc) => Expression::new(
+        ExpressionNode::Binary(Box::new(acc), if sign < 0 { "-".to_owned() } else { "+".to_owned() }, Box::new(term.to_owned())),
+        pos,
+      ),
+    })
+  }
+
+  result.unwrap_or_else(|| Expression::new(ExpressionNode::Number(0.0), pos))
+}
+
+fn flatten<'v>(e: &Expression<'v>, sign: i64, terms: &mut Vec<(i64, Expression<'v>)>) {
+  match e.node {
+    ExpressionNode::Binary(ref l, ref op, ref r) if op == "+" || op == "-" => {
+      flatten(l, sign, terms);
+      flatten(r, if op == "+" { sign } else { -sign }, terms);
+    },
+
+    _ => terms.push((sign, e.to_owned())),
+  }
+}
+
+// no structural `PartialEq` exists on the full AST, so cancellation compares arithmetic nodes
+// operator by operator and child by child, ignoring source positions, and falls back to the
+// `Debug` representation of the position-free leaves (`Number`/`Identifier`)
+fn structurally_equal(a: &Expression, b: &Expression) -> bool {
+  match (&a.node, &b.node) {
+    (&ExpressionNode::Binary(ref al, ref ao, ref ar), &ExpressionNode::Binary(ref bl, ref bo, ref br)) =>
+      ao == bo && structurally_equal(al, bl) && structurally_equal(ar, br),
+
+    _ => format!("{:?}", a.node) == format!("{:?}", b.node),
+  }
+}
+
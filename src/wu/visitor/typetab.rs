@@ -1,7 +1,7 @@
 use std::rc::Rc;
 use std::cell::RefCell;
 use std::fmt;
-use std::collections::HashMap;
+use std::collections::{ HashMap, HashSet, };
 
 use super::*;
 
@@ -11,6 +11,9 @@ use super::*;
 pub struct TypeTab {
     pub parent:  Option<Rc<TypeTab>>,
     pub types:   RefCell<Vec<Type>>,
+    // quantified `TypeNode::Var` ids for the scheme stored at the matching index in `types`;
+    // empty means the binding is monomorphic
+    pub schemes: RefCell<Vec<Vec<u32>>>,
     pub aliases: RefCell<HashMap<String, Type>>,
 }
 
@@ -18,6 +21,7 @@ impl TypeTab {
     pub fn new(parent: Rc<TypeTab>, types: &[Type], aliases: &HashMap<String, Type>) -> TypeTab {
         TypeTab {
             parent:  Some(parent),
+            schemes: RefCell::new(vec![Vec::new(); types.len()]),
             types:   RefCell::new(types.to_owned()),
             aliases: RefCell::new(aliases.clone()),
         }
@@ -27,6 +31,7 @@ impl TypeTab {
         TypeTab {
             parent:  None,
             types:   RefCell::new(Vec::new()),
+            schemes: RefCell::new(Vec::new()),
             aliases: RefCell::new(HashMap::new()),
         }
     }
@@ -62,6 +67,56 @@ impl TypeTab {
         }
     }
 
+    pub fn set_scheme(&self, index: usize, env_index: usize, quantified: Vec<u32>) -> Response<()> {
+        if env_index == 0usize {
+            match self.schemes.borrow_mut().get_mut(index) {
+                Some(v) => {
+                    *v = quantified;
+                    Ok(())
+                },
+                None => Err(make_error(None, format!("invalid type index: {}", index)))
+            }
+        } else {
+            match self.parent {
+                Some(ref p) => p.set_scheme(index, env_index - 1, quantified),
+                None        => Err(make_error(None, format!("invalid type env index: {}", env_index)))
+            }
+        }
+    }
+
+    pub fn get_scheme(&self, index: usize, env_index: usize) -> Response<Vec<u32>> {
+        if env_index == 0 {
+            match self.schemes.borrow().get(index) {
+                Some(v) => Ok(v.clone()),
+                None => Err(make_error(None, format!("invalid type index: {}", index)))
+            }
+        } else {
+            match self.parent {
+                Some(ref p) => p.get_scheme(index, env_index - 1),
+                None => Err(make_error(None, format!("invalid type index: {}", index)))
+            }
+        }
+    }
+
+    // free type variables appearing anywhere in this scope chain; used to decide which
+    // variables a freshly generalized scheme is allowed to quantify over
+    pub fn free_vars(&self) -> HashSet<u32> {
+        let mut free = HashSet::new();
+
+        if let Some(ref p) = self.parent {
+            free.extend(p.free_vars());
+        }
+
+        for (t, quantified) in self.types.borrow().iter().zip(self.schemes.borrow().iter()) {
+            let mut vars = HashSet::new();
+            collect_free_vars(t, &mut vars);
+
+            free.extend(vars.into_iter().filter(|v| !quantified.contains(v)));
+        }
+
+        free
+    }
+
     pub fn set_alias(&self, env_index: usize, name: &str, t: Type) -> Response<()> {
         if env_index == 0 {
             let mut aliases = self.aliases.borrow_mut();
@@ -131,7 +186,28 @@ impl TypeTab {
     }
 
     pub fn grow(&mut self) {
-        RefCell::borrow_mut(&self.types).push(Type::nil())
+        RefCell::borrow_mut(&self.types).push(Type::nil());
+        RefCell::borrow_mut(&self.schemes).push(Vec::new());
+    }
+}
+
+fn collect_free_vars(t: &Type, out: &mut HashSet<u32>) {
+    match t.node {
+        TypeNode::Var(n)        => { out.insert(n); },
+        TypeNode::Set(ref content) => for element in content {
+            collect_free_vars(element, out)
+        },
+        TypeNode::Fun(ref params, ref return_type) => {
+            for param in params {
+                collect_free_vars(param, out)
+            }
+
+            collect_free_vars(return_type, out)
+        },
+        TypeNode::Record(ref fields) => for &(_, ref field_type) in fields {
+            collect_free_vars(field_type, out)
+        },
+        _ => (),
     }
 }
 
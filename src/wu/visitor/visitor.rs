@@ -2,11 +2,16 @@ use super::*;
 use super::super::error::Response::Wrong;
 
 use std::fmt::{ self, Write, Formatter, Display, };
+use std::collections::{ HashMap, HashSet, };
+
+// a substitution maps unification variables to the types they've been bound to; it's threaded
+// through the visitor and applied whenever a type needs to be read back out in resolved form
+pub type Substitution = HashMap<u32, Type>;
 
 #[derive(Debug, Clone)]
 pub enum TypeNode {
-  Int,
-  Float,
+  Int { bits: u8, signed: bool },
+  Float { bits: u8 },
   Number,
   Bool,
   Str,
@@ -14,6 +19,9 @@ pub enum TypeNode {
   Nil,
   Id(String),
   Set(Vec<Type>),
+  Var(u32),
+  Fun(Vec<Type>, Box<Type>),
+  Record(Vec<(String, Type)>),
 }
 
 impl PartialEq for TypeNode {
@@ -21,12 +29,12 @@ impl PartialEq for TypeNode {
     use self::TypeNode::*;
 
     match (self, other) {
-      (&Int, &Int)       => true,
-      (&Int, &Number)    => true,
-      (&Number, &Int)    => true,
-      (&Float, &Float)   => true,
-      (&Float, &Number)  => true,
-      (&Number, &Float)  => true,
+      (&Int { bits: ba, signed: sa }, &Int { bits: bb, signed: sb }) => ba == bb && sa == sb,
+      (&Int { .. }, &Number)    => true,
+      (&Number, &Int { .. })    => true,
+      (&Float { bits: ba }, &Float { bits: bb }) => ba == bb,
+      (&Float { .. }, &Number)  => true,
+      (&Number, &Float { .. })  => true,
       (&Number, &Number) => true,
       (&Bool, &Bool)     => true,
       (&Str, &Str)       => true,
@@ -34,6 +42,11 @@ impl PartialEq for TypeNode {
       (&Nil, &Nil)       => true,
       (&Id(ref a), &Id(ref b))   => a == b,
       (&Set(ref a), &Set(ref b)) => a == b,
+      (&Var(a), &Var(b))         => a == b,
+      (&Fun(ref pa, ref ra), &Fun(ref pb, ref rb)) => pa == pb && ra == rb,
+      (&Record(ref a), &Record(ref b)) => a.len() == b.len() && a.iter().all(|&(ref name, ref t)|
+        b.iter().any(|&(ref n2, ref t2)| n2 == name && t2 == t)
+      ),
       _                          => false,
     }
   }
@@ -55,8 +68,8 @@ impl Display for TypeNode {
 
     match *self {
       Number    => write!(f, "number"),
-      Int       => write!(f, "int"),
-      Float     => write!(f, "float"),
+      Int { bits, signed }   => write!(f, "{}{}", if signed { "i" } else { "u" }, bits),
+      Float { bits }         => write!(f, "f{}", bits),
       Bool      => write!(f, "bool"),
       Str       => write!(f, "string"),
       Char      => write!(f, "char"),
@@ -71,6 +84,33 @@ impl Display for TypeNode {
 
         write!(f, ")")
       },
+      Var(n) => write!(f, "'t{}", n),
+      Fun(ref params, ref return_type) => {
+        write!(f, "(")?;
+
+        for (i, param) in params.iter().enumerate() {
+          if i > 0 {
+            write!(f, ", ")?
+          }
+
+          write!(f, "{}", param)?
+        }
+
+        write!(f, ") {}", return_type)
+      },
+      Record(ref fields) => {
+        write!(f, "struct {{ ")?;
+
+        for (i, &(ref name, ref t)) in fields.iter().enumerate() {
+          if i > 0 {
+            write!(f, ", ")?
+          }
+
+          write!(f, "{}: {}", name, t)?
+        }
+
+        write!(f, " }}")
+      },
     }
   }
 }
@@ -145,11 +185,52 @@ impl Type {
   }
 
   pub fn int() -> Type {
-    Type::new(TypeNode::Int, TypeMode::Regular)
+    Type::i32()
   }
 
   pub fn float() -> Type {
-    Type::new(TypeNode::Float, TypeMode::Regular)
+    Type::f64()
+  }
+
+  pub fn i8()   -> Type { Type::new(TypeNode::Int { bits: 8,   signed: true  }, TypeMode::Regular) }
+  pub fn i16()  -> Type { Type::new(TypeNode::Int { bits: 16,  signed: true  }, TypeMode::Regular) }
+  pub fn i32()  -> Type { Type::new(TypeNode::Int { bits: 32,  signed: true  }, TypeMode::Regular) }
+  pub fn i64()  -> Type { Type::new(TypeNode::Int { bits: 64,  signed: true  }, TypeMode::Regular) }
+  pub fn i128() -> Type { Type::new(TypeNode::Int { bits: 128, signed: true  }, TypeMode::Regular) }
+
+  pub fn u8()   -> Type { Type::new(TypeNode::Int { bits: 8,   signed: false }, TypeMode::Regular) }
+  pub fn u16()  -> Type { Type::new(TypeNode::Int { bits: 16,  signed: false }, TypeMode::Regular) }
+  pub fn u32()  -> Type { Type::new(TypeNode::Int { bits: 32,  signed: false }, TypeMode::Regular) }
+  pub fn u64()  -> Type { Type::new(TypeNode::Int { bits: 64,  signed: false }, TypeMode::Regular) }
+  pub fn u128() -> Type { Type::new(TypeNode::Int { bits: 128, signed: false }, TypeMode::Regular) }
+
+  pub fn f32() -> Type { Type::new(TypeNode::Float { bits: 32 }, TypeMode::Regular) }
+  pub fn f64() -> Type { Type::new(TypeNode::Float { bits: 64 }, TypeMode::Regular) }
+
+  // maps a parsed type name (`i128`, `u8`, `f32`, ...) to its sized `TypeNode`, if it is one.
+  //
+  // NOTE: `bits`/`signed` are only consumed here, by the type checker — nothing in this tree
+  // lowers `ExpressionNode::Number` to bytecode yet (the `compiler`/`interpreter` stage that
+  // `main.rs` imports from `wu::interpreter` isn't part of this snapshot), so there is no
+  // codegen path to carry the chosen width into. Once that stage exists, literal lowering
+  // should match on `TypeNode::Int { bits, signed }`/`Float { bits }` from the checked type of
+  // each `Number` expression to pick the emitted width instead of assuming a single default.
+  pub fn sized_from_name(name: &str) -> Option<Type> {
+    match name {
+      "i8"   => Some(Type::i8()),
+      "i16"  => Some(Type::i16()),
+      "i32"  => Some(Type::i32()),
+      "i64"  => Some(Type::i64()),
+      "i128" => Some(Type::i128()),
+      "u8"   => Some(Type::u8()),
+      "u16"  => Some(Type::u16()),
+      "u32"  => Some(Type::u32()),
+      "u64"  => Some(Type::u64()),
+      "u128" => Some(Type::u128()),
+      "f32"  => Some(Type::f32()),
+      "f64"  => Some(Type::f64()),
+      _      => None,
+    }
   }
 
   pub fn string() -> Type {
@@ -182,6 +263,9 @@ pub struct Visitor<'v> {
   pub typetab: TypeTab<'v>,
   pub source:  &'v Source,
   pub ast:     &'v Vec<Statement<'v>>,
+
+  substitution: Substitution,
+  next_var:     u32,
 }
 
 impl<'v> Visitor<'v> {
@@ -191,6 +275,23 @@ impl<'v> Visitor<'v> {
       typetab: TypeTab::global(),
       source,
       ast,
+
+      substitution: Substitution::new(),
+      next_var:     0,
+    }
+  }
+
+  // like `new`, but carries over bindings from a previous visitor instead of starting from an
+  // empty global scope — used by the REPL to keep earlier entries in scope across evaluations
+  pub fn with_state(source: &'v Source, ast: &'v Vec<Statement<'v>>, symtab: SymTab<'v>, typetab: TypeTab<'v>) -> Self {
+    Visitor {
+      symtab,
+      typetab,
+      source,
+      ast,
+
+      substitution: Substitution::new(),
+      next_var:     0,
     }
   }
 
@@ -199,15 +300,326 @@ impl<'v> Visitor<'v> {
       self.visit_statement(&statement)?
     }
 
+    for t in self.typetab.types.borrow_mut().iter_mut() {
+      *t = self.apply_substitution(t)
+    }
+
+    Ok(())
+  }
+
+  // the type of whatever a statement binds or evaluates to; used by the REPL to report an
+  // inferred type after each entry
+  pub fn type_of(&mut self, statement: &'v Statement<'v>) -> Result<Type, ()> {
+    use self::StatementNode::*;
+
+    match statement.node {
+      Expression(ref e)                                    => self.type_expression(e),
+      Variable(_, ref left, _) | Constant(_, ref left, _)  => self.type_expression(left),
+    }
+  }
+
+  // produces a fresh, never-before-seen type variable
+  fn fresh(&mut self) -> Type {
+    let var = self.next_var;
+    self.next_var += 1;
+
+    Type::new(TypeNode::Var(var), TypeMode::Regular)
+  }
+
+  // reads a type back out with every bound variable replaced by what it's currently bound to
+  fn apply_substitution(&self, t: &Type) -> Type {
+    use self::TypeNode::*;
+
+    match t.node {
+      Var(n) => match self.substitution.get(&n) {
+        Some(bound) => self.apply_substitution(bound),
+        None        => t.to_owned(),
+      },
+
+      Set(ref content) => Type::new(
+        Set(content.iter().map(|e| self.apply_substitution(e)).collect()),
+        t.mode.clone(),
+      ),
+
+      Fun(ref params, ref return_type) => Type::new(
+        Fun(
+          params.iter().map(|e| self.apply_substitution(e)).collect(),
+          Box::new(self.apply_substitution(return_type)),
+        ),
+        t.mode.clone(),
+      ),
+
+      Record(ref fields) => Type::new(
+        Record(fields.iter().map(|&(ref name, ref e)| (name.to_owned(), self.apply_substitution(e))).collect()),
+        t.mode.clone(),
+      ),
+
+      _ => t.to_owned(),
+    }
+  }
+
+  fn occurs(&self, n: u32, t: &Type) -> bool {
+    use self::TypeNode::*;
+
+    match t.node {
+      Var(m)              => m == n,
+      Set(ref content)    => content.iter().any(|e| self.occurs(n, e)),
+      Fun(ref params, ref return_type) => params.iter().any(|e| self.occurs(n, e)) || self.occurs(n, return_type),
+      Record(ref fields)  => fields.iter().any(|&(_, ref t)| self.occurs(n, t)),
+      _                   => false,
+    }
+  }
+
+  fn bind(&mut self, n: u32, t: &Type, pos: (usize, usize)) -> Result<(), ()> {
+    if let TypeNode::Var(m) = t.node {
+      if m == n {
+        return Ok(())
+      }
+    }
+
+    if self.occurs(n, t) {
+      return Err(
+        response!(
+          Wrong(format!("infinite type: `{}` occurs in `{}`", Type::new(TypeNode::Var(n), TypeMode::Regular), t)),
+          self.source.file,
+          pos
+        )
+      )
+    }
+
+    self.substitution.insert(n, t.to_owned());
+
     Ok(())
   }
 
+  // unifies `a` and `b` under the current substitution, recording new variable bindings as
+  // they're discovered; two distinct concrete constructors are a type error
+  fn unify(&mut self, a: &Type, b: &Type, pos: (usize, usize)) -> Result<(), ()> {
+    use self::TypeNode::*;
+
+    let a = self.apply_substitution(a);
+    let b = self.apply_substitution(b);
+
+    match (&a.node, &b.node) {
+      (&Var(n), _) => self.bind(n, &b, pos),
+      (_, &Var(n)) => self.bind(n, &a, pos),
+
+      (&Set(ref xs), &Set(ref ys)) => {
+        if xs.len() != ys.len() {
+          return Err(
+            response!(
+              Wrong(format!("mismatched types, expected `{}` got `{}`", a.node, b.node)),
+              self.source.file,
+              pos
+            )
+          )
+        }
+
+        for (x, y) in xs.iter().zip(ys.iter()) {
+          self.unify(x, y, pos)?
+        }
+
+        Ok(())
+      },
+
+      (&Fun(ref pa, ref ra), &Fun(ref pb, ref rb)) => {
+        if pa.len() != pb.len() {
+          return Err(
+            response!(
+              Wrong(format!("mismatched types, expected `{}` got `{}`", a.node, b.node)),
+              self.source.file,
+              pos
+            )
+          )
+        }
+
+        for (x, y) in pa.iter().zip(pb.iter()) {
+          self.unify(x, y, pos)?
+        }
+
+        self.unify(ra, rb, pos)
+      },
+
+      (&Record(ref fa), &Record(ref fb)) => {
+        if fa.len() != fb.len() {
+          return Err(
+            response!(
+              Wrong(format!("mismatched types, expected `{}` got `{}`", a.node, b.node)),
+              self.source.file,
+              pos
+            )
+          )
+        }
+
+        for &(ref name, ref field_a) in fa {
+          match fb.iter().find(|&&(ref n, _)| n == name) {
+            Some(&(_, ref field_b)) => self.unify(field_a, field_b, pos)?,
+            None => return Err(
+              response!(
+                Wrong(format!("no field `{}` on type `{}`", name, b.node)),
+                self.source.file,
+                pos
+              )
+            ),
+          }
+        }
+
+        Ok(())
+      },
+
+      _ => if a.node == b.node {
+        Ok(())
+      } else {
+        Err(
+          response!(
+            Wrong(format!("mismatched types, expected `{}` got `{}`", a.node, b.node)),
+            self.source.file,
+            pos
+          )
+        )
+      },
+    }
+  }
+
+  fn collect_vars(t: &Type, out: &mut HashSet<u32>) {
+    match t.node {
+      TypeNode::Var(n)           => { out.insert(n); },
+      TypeNode::Set(ref content) => for e in content {
+        Visitor::collect_vars(e, out)
+      },
+      TypeNode::Fun(ref params, ref return_type) => {
+        for e in params {
+          Visitor::collect_vars(e, out)
+        }
+
+        Visitor::collect_vars(return_type, out)
+      },
+      TypeNode::Record(ref fields) => for &(_, ref t) in fields {
+        Visitor::collect_vars(t, out)
+      },
+      _ => (),
+    }
+  }
+
+  // quantifies every type variable in `t` that isn't free in the surrounding environment,
+  // turning a monomorphic inferred type into a let-polymorphic scheme
+  fn generalize(&self, t: &Type) -> Vec<u32> {
+    let mut free = HashSet::new();
+    Visitor::collect_vars(t, &mut free);
+
+    let env_free = self.typetab.free_vars();
+
+    free.into_iter().filter(|v| !env_free.contains(v)).collect()
+  }
+
+  fn substitute_vars(t: &Type, mapping: &HashMap<u32, Type>) -> Type {
+    match t.node {
+      TypeNode::Var(n) => mapping.get(&n).cloned().unwrap_or_else(|| t.to_owned()),
+      TypeNode::Set(ref content) => Type::new(
+        TypeNode::Set(content.iter().map(|e| Visitor::substitute_vars(e, mapping)).collect()),
+        t.mode.clone(),
+      ),
+      TypeNode::Fun(ref params, ref return_type) => Type::new(
+        TypeNode::Fun(
+          params.iter().map(|e| Visitor::substitute_vars(e, mapping)).collect(),
+          Box::new(Visitor::substitute_vars(return_type, mapping)),
+        ),
+        t.mode.clone(),
+      ),
+      TypeNode::Record(ref fields) => Type::new(
+        TypeNode::Record(fields.iter().map(|&(ref name, ref e)| (name.to_owned(), Visitor::substitute_vars(e, mapping))).collect()),
+        t.mode.clone(),
+      ),
+      _ => t.to_owned(),
+    }
+  }
+
+  // replaces every quantified variable in a scheme with a fresh one, so each use site of a
+  // polymorphic binding gets its own independent type variables
+  fn instantiate(&mut self, scheme: &Type, quantified: &[u32]) -> Type {
+    let mapping = quantified.iter().map(|&v| (v, self.fresh())).collect::<HashMap<_, _>>();
+
+    Visitor::substitute_vars(scheme, &mapping)
+  }
+
+  // replaces every `TypeNode::Id(name)` appearing in `t` with the type it was declared as via
+  // a `Name: type = <type-expr>` alias, so an alias is interchangeable with its definition
+  // during checking; errors on an unknown name or a cycle of aliases referring to each other
+  fn resolve_type(&self, t: &Type) -> Result<Type, ()> {
+    self.resolve_type_visiting(t, &mut HashSet::new())
+  }
+
+  fn resolve_type_visiting(&self, t: &Type, visiting: &mut HashSet<String>) -> Result<Type, ()> {
+    match t.node {
+      TypeNode::Id(ref name) => {
+        // sized primitives (`i128`, `u8`, `f32`, ...) are builtins, not user aliases — resolve
+        // them directly before ever touching `TypeTab`'s alias table
+        if let Some(sized) = Type::sized_from_name(name) {
+          return Ok(sized)
+        }
+
+        if !visiting.insert(name.clone()) {
+          return Err(
+            response!(
+              Wrong(format!("cyclic type alias `{}`", name)),
+              self.source.file,
+              (0, 0)
+            )
+          )
+        }
+
+        let aliased = self.typetab.get_alias(name, 0).map_err(|_| {
+          response!(
+            Wrong(format!("unknown type `{}`", name)),
+            self.source.file,
+            (0, 0)
+          )
+        })?;
+
+        self.resolve_type_visiting(&aliased, visiting)
+      },
+
+      // each child gets its own clone of `visiting`: the set tracks the alias chain on the path
+      // from root to the node being resolved, so two independent siblings referring to the same
+      // alias (e.g. both halves of `(Point, Point)`) must not be mistaken for a cycle
+      TypeNode::Set(ref content) => {
+        let resolved = content.iter()
+          .map(|e| self.resolve_type_visiting(e, &mut visiting.clone()))
+          .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Type::new(TypeNode::Set(resolved), t.mode.clone()))
+      },
+
+      TypeNode::Fun(ref params, ref return_type) => {
+        let params = params.iter()
+          .map(|e| self.resolve_type_visiting(e, &mut visiting.clone()))
+          .collect::<Result<Vec<_>, _>>()?;
+
+        let return_type = self.resolve_type_visiting(return_type, &mut visiting.clone())?;
+
+        Ok(Type::new(TypeNode::Fun(params, Box::new(return_type)), t.mode.clone()))
+      },
+
+      TypeNode::Record(ref fields) => {
+        let fields = fields.iter()
+          .map(|&(ref name, ref field_type)| Ok((name.to_owned(), self.resolve_type_visiting(field_type, &mut visiting.clone())?)))
+          .collect::<Result<Vec<_>, ()>>()?;
+
+        Ok(Type::new(TypeNode::Record(fields), t.mode.clone()))
+      },
+
+      _ => Ok(t.to_owned()),
+    }
+  }
+
   pub fn visit_statement(&mut self, statement: &'v Statement<'v>) -> Result<(), ()> {
     use self::StatementNode::*;
 
     match statement.node {
       Expression(ref expression) => self.visit_expression(expression),
 
+      TypeAlias(ref name, ref aliased) => self.typetab.set_alias(0, name, aliased.to_owned()),
+
       Variable(_, ref left, _) => match left.node {
         ExpressionNode::Identifier(_) | ExpressionNode::Set(_) => self.visit_variable(&statement.node),
         _ => Ok(())
@@ -252,6 +664,39 @@ impl<'v> Visitor<'v> {
         Ok(())
       }
 
+      Function(ref params, _, ref body) => {
+        self.typetab.grow();
+
+        for &(ref name, ref param_type) in params {
+          let index      = self.symtab.add_name(name);
+          let param_type = self.resolve_type(param_type)?;
+
+          self.typetab.set_type(index, 0, param_type)?
+        }
+
+        self.visit_expression(body)
+      },
+
+      Call(ref callee, ref args) => {
+        self.visit_expression(callee)?;
+
+        for arg in args {
+          self.visit_expression(arg)?
+        }
+
+        Ok(())
+      },
+
+      Record(ref fields) => {
+        for &(_, ref value) in fields {
+          self.visit_expression(value)?
+        }
+
+        Ok(())
+      },
+
+      Field(ref base, _) => self.visit_expression(base),
+
       _ => Ok(())
     }
   }
@@ -276,7 +721,9 @@ impl<'v> Visitor<'v> {
             let right_type = self.type_expression(&right)?;
 
             if variable_type.node != TypeNode::Nil {
-              if variable_type != &right_type {
+              let variable_type = self.resolve_type(variable_type)?;
+
+              if let Err(_) = self.unify(&variable_type, &right_type, right.pos) {
                 return Err(
                   response!(
                     Wrong(format!("mismatched types, expected type `{}` got `{}`", variable_type.node, right_type)),
@@ -284,14 +731,20 @@ impl<'v> Visitor<'v> {
                     right.pos
                   )
                 )
-              } else {
-                self.typetab.set_type(index, 0, variable_type.to_owned())?
               }
+
+              let resolved = self.apply_substitution(&variable_type);
+              self.typetab.set_type(index, 0, resolved)?
             } else {
-              self.typetab.set_type(index, 0, right_type)?
+              let resolved   = self.apply_substitution(&right_type);
+              let quantified = self.generalize(&resolved);
+
+              self.typetab.set_type(index, 0, resolved)?;
+              self.typetab.set_scheme(index, 0, quantified)?
             }
           } else {
-            self.typetab.set_type(index, 0, variable_type.to_owned())?
+            let resolved = self.resolve_type(variable_type)?;
+            self.typetab.set_type(index, 0, resolved)?
           }
         },
 
@@ -331,7 +784,11 @@ impl<'v> Visitor<'v> {
 
     if let &StatementNode::Constant(ref constant_type, ref left, ref right) = constant {
       match left.node {
-        Identifier(ref name) => {
+        Identifier(ref name) => if let ExpressionNode::Struct(ref fields) = right.node {
+          let field_types = fields.iter().map(|&(ref n, ref t)| (n.to_owned(), t.to_owned())).collect();
+
+          self.typetab.set_alias(0, name, Type::new(TypeNode::Record(field_types), TypeMode::Regular))?
+        } else {
           let index = if let Some((index, _)) = self.symtab.get_name(name) {
             index
           } else {
@@ -345,7 +802,9 @@ impl<'v> Visitor<'v> {
           let right_type = self.type_expression(right)?;
 
           if constant_type.node != TypeNode::Nil {
-            if constant_type != &right_type {
+            let constant_type = self.resolve_type(constant_type)?;
+
+            if let Err(_) = self.unify(&constant_type, &right_type, right.pos) {
               return Err(
                 response!(
                   Wrong(format!("mismatched types, expected type `{}` got `{}`", constant_type.node, right_type)),
@@ -353,11 +812,16 @@ impl<'v> Visitor<'v> {
                   right.pos
                 )
               )
-            } else {
-              self.typetab.set_type(index, 0, constant_type.to_owned())?
             }
+
+            let resolved = self.apply_substitution(&constant_type);
+            self.typetab.set_type(index, 0, resolved)?
           } else {
-            self.typetab.set_type(index, 0, right_type)?
+            let resolved   = self.apply_substitution(&right_type);
+            let quantified = self.generalize(&resolved);
+
+            self.typetab.set_type(index, 0, resolved)?;
+            self.typetab.set_scheme(index, 0, quantified)?
           }
         },
 
@@ -399,7 +863,14 @@ impl<'v> Visitor<'v> {
 
     let t = match expression.node {
       Identifier(ref name) => if let Some((index, env_index)) = self.symtab.get_name(name) {
-        self.typetab.get_type(index, env_index)?
+        let scheme     = self.typetab.get_type(index, env_index)?;
+        let quantified = self.typetab.get_scheme(index, env_index)?;
+
+        if quantified.is_empty() {
+          scheme
+        } else {
+          self.instantiate(&scheme, &quantified)
+        }
       } else {
         unreachable!()
       },
@@ -409,7 +880,116 @@ impl<'v> Visitor<'v> {
       Bool(_)   => Type::bool(),
       Number(_) => Type::number(),
 
-      _ => Type::nil()
+      Function(ref params, ref return_type, ref body) => {
+        let return_type = self.resolve_type(return_type)?;
+
+        let mut param_types = Vec::new();
+
+        self.typetab.grow();
+
+        for &(ref name, ref param_type) in params {
+          let index      = self.symtab.add_name(name);
+          let param_type = self.resolve_type(param_type)?;
+
+          self.typetab.set_type(index, 0, param_type.clone())?;
+          param_types.push(param_type);
+        }
+
+        let body_type = self.type_expression(body)?;
+        self.unify(&return_type, &body_type, body.pos)?;
+
+        Type::new(TypeNode::Fun(param_types, Box::new(return_type)), TypeMode::Regular)
+      },
+
+      // arithmetic's own inference rule: without this arm every `Binary` expression fell through
+      // to the catch-all `self.fresh()` below and never actually constrained its operands
+      Binary(ref left, ref op, ref right) => {
+        let left_type  = self.type_expression(left)?;
+        let right_type = self.type_expression(right)?;
+
+        if op == "+" && left_type.node == TypeNode::Str && right_type.node == TypeNode::Str {
+          Type::string()
+        } else {
+          self.unify(&left_type, &Type::number(), left.pos)?;
+          self.unify(&right_type, &Type::number(), right.pos)?;
+          self.unify(&left_type, &right_type, expression.pos)?;
+
+          self.apply_substitution(&left_type)
+        }
+      },
+
+      Call(ref callee, ref args) => {
+        let callee_type = self.type_expression(callee)?;
+
+        match self.apply_substitution(&callee_type).node {
+          TypeNode::Fun(ref params, ref return_type) => {
+            if params.len() != args.len() {
+              return Err(
+                response!(
+                  Wrong(format!("expected {} argument(s), got {}", params.len(), args.len())),
+                  self.source.file,
+                  expression.pos
+                )
+              )
+            }
+
+            for (param, arg) in params.iter().zip(args.iter()) {
+              let arg_type = self.type_expression(arg)?;
+              self.unify(param, &arg_type, arg.pos)?;
+            }
+
+            (**return_type).to_owned()
+          },
+
+          _ => return Err(
+            response!(
+              Wrong(format!("can't call a value of type `{}`", callee_type)),
+              self.source.file,
+              expression.pos
+            )
+          ),
+        }
+      },
+
+      Record(ref fields) => {
+        let mut field_types = Vec::new();
+
+        for &(ref name, ref value) in fields {
+          let field_type = self.type_expression(value)?;
+          field_types.push((name.to_owned(), field_type));
+        }
+
+        Type::new(TypeNode::Record(field_types), TypeMode::Regular)
+      },
+
+      Field(ref base, ref name) => {
+        let base_type = self.apply_substitution(&self.type_expression(base)?);
+
+        match base_type.node {
+          TypeNode::Record(ref fields) => match fields.iter().find(|&&(ref n, _)| n == name) {
+            Some(&(_, ref field_type)) => field_type.to_owned(),
+            None => return Err(
+              response!(
+                Wrong(format!("no field `{}` on type `{}`", name, base_type)),
+                self.source.file,
+                expression.pos
+              )
+            ),
+          },
+
+          _ => return Err(
+            response!(
+              Wrong(format!("no field `{}` on type `{}`", name, base_type)),
+              self.source.file,
+              expression.pos
+            )
+          ),
+        }
+      },
+
+      // anything we don't yet have a dedicated inference rule for still participates in
+      // unification, rather than silently degrading to `nil`
+      _ => self.fresh(),
     };
 
     Ok(t)